@@ -0,0 +1,148 @@
+//! Bump allocator for `Str` suffixes.
+//!
+//! Building many `Str` values one at a time means each suffix does an
+//! independent call into the global allocator. `StrArena` instead carves
+//! suffixes out of large pre-reserved chunks, bumping a cursor forward on
+//! each allocation, and only returns memory to the allocator when the whole
+//! arena (and all its chunks) drops. Blocks given back via [`StrArena::recycle`]
+//! are kept on a free-list keyed by size so repeated construct/recycle cycles
+//! of similarly sized strings reuse memory instead of bumping further.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::ptr::null_mut;
+
+use crate::{alloc_bytes, dealloc_bytes, StrN, DEFAULT_PREFIX_LENGTH};
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+struct Chunk {
+    ptr: *mut u8,
+    size: usize,
+    used: usize,
+}
+
+impl Chunk {
+    fn new(size: usize) -> Self {
+        let ptr = unsafe { alloc_bytes(size) };
+        Self { ptr, size, used: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.size - self.used
+    }
+
+    fn bump(&mut self, request: usize) -> *mut u8 {
+        let ptr = unsafe { self.ptr.add(self.used) };
+        self.used += request;
+        ptr
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        unsafe { dealloc_bytes(self.ptr, self.size) }
+    }
+}
+
+/// A bump-allocating pool for suffix storage shared by many `Str` values.
+///
+/// `Str` values handed out by the arena borrow their `suffix` pointer from
+/// one of the arena's chunks, so they must not outlive the `StrArena` that
+/// created them. Nothing in `Str`'s type enforces that, which is why
+/// [`StrArena::from`] and [`StrArena::recycle`] are `unsafe fn`s with the
+/// invariant spelled out on each. The [`Str::from`](crate::Str::from) path
+/// remains untouched for callers that don't want pooled allocation.
+pub struct StrArena<const PREFIX: usize = DEFAULT_PREFIX_LENGTH> {
+    chunk_size: usize,
+    chunks: Vec<Chunk>,
+    free_list: BTreeMap<usize, Vec<*mut u8>>,
+}
+
+impl<const PREFIX: usize> StrArena<PREFIX> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    #[must_use]
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunk_size,
+            chunks: Vec::new(),
+            free_list: BTreeMap::new(),
+        }
+    }
+
+    /// Builds a `Str` whose suffix (if any) lives in this arena.
+    ///
+    /// # Safety
+    /// Nothing in `Str`'s type ties it to this arena's lifetime, so the
+    /// caller must ensure the returned value (and anything it's copied
+    /// into) does not outlive this `StrArena`, and is only ever dropped via
+    /// [`StrArena::recycle`] on this same arena, never via its own `Drop`.
+    #[must_use]
+    pub unsafe fn from(&mut self, str: &str) -> StrN<PREFIX> {
+        let bytes = str.as_bytes();
+        let len = bytes.len();
+        debug_assert!(
+            len < crate::LenType::MAX as usize,
+            "Size of string is above LenType limit."
+        );
+
+        let mut prefix: [u8; PREFIX] = [0; PREFIX];
+        let prefix_len = len.min(PREFIX);
+        prefix[..prefix_len].copy_from_slice(&bytes[..prefix_len]);
+
+        let mut suffix: *mut u8 = null_mut();
+        if len > PREFIX {
+            let suffix_len = len - PREFIX;
+            suffix = self.alloc_suffix(suffix_len);
+            unsafe {
+                core::ptr::copy_nonoverlapping(bytes.as_ptr().add(PREFIX), suffix, suffix_len);
+            }
+        }
+
+        StrN::from_raw_parts(len as crate::LenType, prefix, 0, suffix)
+    }
+
+    /// Returns a `Str`'s suffix block to the free-list so a later
+    /// same-size [`StrArena::from`] call can reuse it instead of bumping.
+    ///
+    /// # Safety
+    /// `str` must have been produced by this exact `StrArena` (via
+    /// [`StrArena::from`]) and not already recycled or otherwise dropped.
+    /// Its suffix memory is not owned by the global allocator, so passing a
+    /// `Str` built by [`Str::from`](crate::Str::from) or by a different
+    /// arena would push a pointer this arena doesn't own onto the
+    /// free-list, corrupting future allocations.
+    // Taking `str` by value is intentional, not an oversight: recycling must
+    // consume the `Str` so the caller can't keep using it (and its dangling
+    // suffix pointer) after the arena reclaims the memory.
+    #[allow(clippy::needless_pass_by_value)]
+    pub unsafe fn recycle(&mut self, str: StrN<PREFIX>) {
+        if str.len() > PREFIX {
+            let size = str.len() - PREFIX;
+            self.free_list.entry(size).or_default().push(str.suffix_ptr());
+        }
+    }
+
+    fn alloc_suffix(&mut self, size: usize) -> *mut u8 {
+        if let Some(free) = self.free_list.get_mut(&size) {
+            if let Some(ptr) = free.pop() {
+                return ptr;
+            }
+        }
+
+        if self.chunks.last().is_none_or(|c| c.remaining() < size) {
+            self.chunks.push(Chunk::new(self.chunk_size.max(size)));
+        }
+        self.chunks.last_mut().unwrap().bump(size)
+    }
+}
+
+impl<const PREFIX: usize> Default for StrArena<PREFIX> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
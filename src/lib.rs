@@ -1,42 +1,90 @@
 #![warn(clippy::all, clippy::pedantic)]
+// Narrowing casts into `LenType`/`CapacityOffsetType` are central to this
+// crate's compact layout. `from`, `StrArena::from` and `reserve` check the
+// new `len`/`capacity_offset` with a `debug_assert!` against the target
+// type's `MAX` rather than a runtime check; the casts in `push_str`,
+// `insert_str` and `truncate` are not checked.
+#![allow(clippy::cast_possible_truncation)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::alloc::{alloc, dealloc};
 use core::slice;
-use core::{alloc::Layout, fmt::Display, ptr::null_mut};
+use core::{alloc::Layout, ptr::null_mut};
+#[cfg(feature = "std")]
+use core::fmt::Display;
+
+mod arena;
+mod prefix_set;
+pub use arena::StrArena;
+pub use prefix_set::PrefixSet;
+
+pub(crate) type CapacityOffsetType = u16;
+pub(crate) type LenType = u32;
+
+/// Allocates `size` bytes via the global allocator.
+///
+/// `size` is always derived from a `len`/`capacity_offset` that already fits
+/// in the type's own limits, so the only way [`Layout::array`] can fail here
+/// is if `size` overflows `isize`, which we'd rather abort on than propagate.
+///
+/// # Safety
+/// The caller must eventually free the returned pointer with a `Layout` of
+/// the same `size` (e.g. via [`dealloc_bytes`]).
+pub(crate) unsafe fn alloc_bytes(size: usize) -> *mut u8 {
+    alloc(Layout::array::<u8>(size).expect("allocation size overflowed"))
+}
+
+/// Frees a pointer previously returned by [`alloc_bytes`] with the same `size`.
+///
+/// # Safety
+/// `ptr` must have been allocated via [`alloc_bytes`] with this exact `size`
+/// and not already freed.
+pub(crate) unsafe fn dealloc_bytes(ptr: *mut u8, size: usize) {
+    dealloc(ptr, Layout::array::<u8>(size).expect("allocation size overflowed"));
+}
 
-const PREFIX_LENGTH: usize = 10;
-type CapacityOffsetType = u16;
-type LenType = u32;
+/// Inline prefix length used by the [`Str`] alias.
+const DEFAULT_PREFIX_LENGTH: usize = 10;
 
 #[repr(C)]
-pub struct Str {
+pub struct StrN<const PREFIX: usize = DEFAULT_PREFIX_LENGTH> {
     len: LenType,
-    prefix: [u8; PREFIX_LENGTH],
+    prefix: [u8; PREFIX],
     capacity_offset: CapacityOffsetType,
     suffix: *mut u8, // len + capacity_offset
 }
 
-impl Str {
+/// `StrN` with the default inline prefix length, so callers who don't need a
+/// custom `PREFIX` can write plain `Str` (and bare calls like
+/// `Str::from("abc")` still get their const-generic argument resolved by
+/// inference, since `Str` names a concrete type rather than a generic one
+/// with a default).
+pub type Str = StrN<DEFAULT_PREFIX_LENGTH>;
+
+impl<const PREFIX: usize> StrN<PREFIX> {
     #[inline]
     #[must_use]
     pub fn from(str: &str) -> Self {
         let bytes = str.as_bytes();
-        let _len = bytes.len();
+        let byte_len = bytes.len();
         debug_assert!(
-            _len < LenType::MAX as usize,
+            byte_len < LenType::MAX as usize,
             "Size of string is above LenType limit."
         );
-        let len = _len as LenType;
-        let mut prefix: [u8; PREFIX_LENGTH] = [0; PREFIX_LENGTH];
+        let len = byte_len as LenType;
+        let mut prefix: [u8; PREFIX] = [0; PREFIX];
         let mut suffix: *mut u8 = null_mut();
 
-        let prefix_len = _len.min(PREFIX_LENGTH);
+        let prefix_len = byte_len.min(PREFIX);
         prefix[..prefix_len].copy_from_slice(&bytes[..prefix_len]);
 
-        if len > PREFIX_LENGTH as LenType {
-            let ptr_len = len as usize - PREFIX_LENGTH;
+        if len > PREFIX as LenType {
+            let ptr_len = len as usize - PREFIX;
             unsafe {
-                suffix = std::alloc::alloc(Layout::array::<u8>(ptr_len).unwrap()); //TODO: Unsafe unwrap?
-                core::ptr::copy(bytes.as_ptr().add(PREFIX_LENGTH), suffix, ptr_len);
+                suffix = alloc_bytes(ptr_len);
+                core::ptr::copy(bytes.as_ptr().add(PREFIX), suffix, ptr_len);
             }
         }
 
@@ -48,28 +96,28 @@ impl Str {
         }
     }
 
-    #[inline(always)]
+    #[inline]
     #[must_use]
     pub fn len(&self) -> usize {
         self.len as usize
     }
 
-    #[inline(always)]
+    #[inline]
     #[must_use]
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
 
-    #[inline(always)]
+    #[inline]
     #[must_use]
     pub fn capacity(&self) -> usize {
-        let prefix_extra_capactiy = PREFIX_LENGTH - PREFIX_LENGTH.min(self.len as usize);
+        let prefix_extra_capactiy = PREFIX - PREFIX.min(self.len as usize);
         self.len as usize + self.capacity_offset as usize + prefix_extra_capactiy
     }
 
     #[inline]
     #[must_use]
-    pub fn starts_with(&self, other: &Str) -> bool {
+    pub fn starts_with(&self, other: &StrN<PREFIX>) -> bool {
         if other.len > self.len {
             return false;
         }
@@ -77,54 +125,178 @@ impl Str {
 
         if !self
             .prefix
-            .starts_with(&other.prefix[..PREFIX_LENGTH.min(other.len())])
+            .starts_with(&other.prefix[..PREFIX.min(other.len())])
         {
             return false;
         }
 
-        if self.len <= PREFIX_LENGTH as LenType || other.len <= PREFIX_LENGTH as LenType {
+        if self.len <= PREFIX as LenType || other.len <= PREFIX as LenType {
             return true;
         }
 
         unsafe {
-            let self_ptr_len = self.len() - PREFIX_LENGTH;
+            let self_ptr_len = self.len() - PREFIX;
             let self_suffix = slice::from_raw_parts(self.suffix, self_ptr_len);
-            let other_ptr_len = other_len - PREFIX_LENGTH;
+            let other_ptr_len = other_len - PREFIX;
             let other_suffix = slice::from_raw_parts(other.suffix, other_ptr_len);
 
             self_suffix.starts_with(other_suffix)
         }
     }
 
+    /// Assembles a `Str` from its raw parts without touching the allocator.
+    ///
+    /// Used by [`StrArena`] to hand back `Str` values whose `suffix` points
+    /// into arena-owned memory rather than a standalone allocation.
+    pub(crate) fn from_raw_parts(
+        len: LenType,
+        prefix: [u8; PREFIX],
+        capacity_offset: CapacityOffsetType,
+        suffix: *mut u8,
+    ) -> Self {
+        Self {
+            len,
+            prefix,
+            capacity_offset,
+            suffix,
+        }
+    }
+
+    pub(crate) fn suffix_ptr(&self) -> *mut u8 {
+        self.suffix
+    }
+
+    pub(crate) fn prefix_bytes(&self) -> &[u8; PREFIX] {
+        &self.prefix
+    }
+
+    /// Ensures at least `request` bytes of spare capacity beyond `len()`,
+    /// growing and reallocating the suffix buffer if needed.
     fn reserve(&mut self, request: usize) {
-        let prefix_extra_capactiy = PREFIX_LENGTH - PREFIX_LENGTH.min(self.len as usize);
+        let prefix_extra_capactiy = PREFIX - PREFIX.min(self.len as usize);
         let current_extra_capacity = self.capacity_offset as usize + prefix_extra_capactiy;
         if current_extra_capacity >= request {
             return;
         }
+
+        let needed_extra = request - current_extra_capacity;
+        let new_capacity_offset = self.capacity_offset as usize + needed_extra;
         debug_assert!(
-            request < CapacityOffsetType::MAX as usize,
+            new_capacity_offset < CapacityOffsetType::MAX as usize,
             "Reserve is above capacity limit."
         );
-        let new_cap_offset = request - current_extra_capacity;
-        let new_ptr_len = self.len as usize + new_cap_offset;
 
-        let new_mem;
+        let old_suffix_len = self.len as usize - PREFIX.min(self.len as usize);
+        let new_suffix_len = old_suffix_len + self.capacity_offset as usize + needed_extra;
 
         unsafe {
-            new_mem = std::alloc::alloc(Layout::array::<u8>(new_ptr_len).unwrap()); //TODO: Unsafe unwrap?
+            let new_mem = alloc_bytes(new_suffix_len);
 
-            if self.len > PREFIX_LENGTH as u32 {
-                core::ptr::copy(self.suffix, new_mem, self.len as usize - PREFIX_LENGTH);
+            if old_suffix_len > 0 {
+                core::ptr::copy_nonoverlapping(self.suffix, new_mem, old_suffix_len);
             }
             if !self.suffix.is_null() {
-                let old_total_cap = self.len + self.capacity_offset - PREFIX_LENGTH;
-                std::alloc::dealloc(ptr, Layout::array::<u8>(old_total_cap)).unwrap()
+                let old_cap = old_suffix_len + self.capacity_offset as usize;
+                dealloc_bytes(self.suffix, old_cap);
+            }
+            self.suffix = new_mem;
+        }
+        self.capacity_offset = new_capacity_offset as CapacityOffsetType;
+    }
+
+    /// Appends `str`, filling any remaining inline prefix space before
+    /// spilling into the heap suffix.
+    pub fn push_str(&mut self, str: &str) {
+        let bytes = str.as_bytes();
+        if bytes.is_empty() {
+            return;
+        }
+        self.reserve(bytes.len());
+
+        let old_len = self.len as usize;
+        let prefix_free = PREFIX - PREFIX.min(old_len);
+        let into_prefix = prefix_free.min(bytes.len());
+        if into_prefix > 0 {
+            self.prefix[old_len..old_len + into_prefix].copy_from_slice(&bytes[..into_prefix]);
+        }
+
+        let into_suffix = &bytes[into_prefix..];
+        if !into_suffix.is_empty() {
+            let suffix_used = old_len.saturating_sub(PREFIX);
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    into_suffix.as_ptr(),
+                    self.suffix.add(suffix_used),
+                    into_suffix.len(),
+                );
             }
+            self.capacity_offset -= into_suffix.len() as CapacityOffsetType;
+        }
+
+        self.len = (old_len + bytes.len()) as LenType;
+    }
+
+    /// Appends a single `char`, encoded as UTF-8.
+    pub fn push(&mut self, c: char) {
+        let mut buf = [0u8; 4];
+        self.push_str(c.encode_utf8(&mut buf));
+    }
+
+    /// Shortens the string to `new_len` bytes, turning the dropped tail into
+    /// spare capacity. No-op if `new_len >= self.len()`.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        let old_suffix_len = self.len() - PREFIX.min(self.len());
+        let new_suffix_len = new_len - PREFIX.min(new_len);
+        let freed = old_suffix_len - new_suffix_len;
+        self.capacity_offset += freed as CapacityOffsetType;
+        if new_len < PREFIX {
+            self.prefix[new_len..PREFIX.min(self.len())].fill(0);
+        }
+        self.len = new_len as LenType;
+    }
+
+    /// Truncates the string to be empty, keeping its allocated capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Inserts `str` at byte index `idx`, shifting the existing tail right.
+    ///
+    /// # Panics
+    /// Panics if `idx > self.len()`.
+    pub fn insert_str(&mut self, idx: usize, str: &str) {
+        assert!(idx <= self.len(), "Insert index out of string bounds!");
+        let bytes = str.as_bytes();
+        if bytes.is_empty() {
+            return;
+        }
+        let old_len = self.len();
+        self.reserve(bytes.len());
+
+        for i in (idx..old_len).rev() {
+            let byte = self[i];
+            self.write_byte(i + bytes.len(), byte);
+        }
+        for (offset, &byte) in bytes.iter().enumerate() {
+            self.write_byte(idx + offset, byte);
+        }
+
+        let new_len = old_len + bytes.len();
+        let old_suffix_len = old_len - PREFIX.min(old_len);
+        let new_suffix_len = new_len - PREFIX.min(new_len);
+        self.capacity_offset -= (new_suffix_len - old_suffix_len) as CapacityOffsetType;
+        self.len = new_len as LenType;
+    }
+
+    fn write_byte(&mut self, index: usize, byte: u8) {
+        if index < PREFIX {
+            self.prefix[index] = byte;
+        } else {
+            unsafe { *self.suffix.add(index - PREFIX) = byte }
         }
-        self.suffix = new_mem;
-        self.capacity_offset = new_cap_offset as u16;
-        // if old_ptr:
     }
 }
 
@@ -132,7 +304,7 @@ pub trait StartsWithStr {
     fn starts_with(&self, other: &str) -> bool;
 }
 
-impl StartsWithStr for Str {
+impl<const PREFIX: usize> StartsWithStr for StrN<PREFIX> {
     #[inline]
     fn starts_with(&self, other: &str) -> bool {
         if other.len() > self.len() {
@@ -142,37 +314,37 @@ impl StartsWithStr for Str {
 
         if !self
             .prefix
-            .starts_with(&other_bytes[..PREFIX_LENGTH.min(other.len())])
+            .starts_with(&other_bytes[..PREFIX.min(other.len())])
         {
             return false;
         }
 
         unsafe {
-            let self_ptr_len = self.len() - PREFIX_LENGTH;
+            let self_ptr_len = self.len() - PREFIX;
             let self_suffix = slice::from_raw_parts(self.suffix, self_ptr_len);
 
-            self_suffix.starts_with(&other_bytes[PREFIX_LENGTH..])
+            self_suffix.starts_with(&other_bytes[PREFIX..])
         }
     }
 }
 
-impl core::ops::Index<usize> for Str {
+impl<const PREFIX: usize> core::ops::Index<usize> for StrN<PREFIX> {
     type Output = u8;
 
     #[inline]
     fn index(&self, index: usize) -> &u8 {
-        assert!(!(index >= self.len()), "Indexing outside of string length!");
+        assert!(index < self.len(), "Indexing outside of string length!");
 
-        if index >= PREFIX_LENGTH {
-            unsafe { return &*self.suffix.add(index - PREFIX_LENGTH) }
+        if index >= PREFIX {
+            unsafe { return &*self.suffix.add(index - PREFIX) }
         }
         &self.prefix[index]
     }
 }
 
-impl PartialEq for Str {
+impl<const PREFIX: usize> PartialEq for StrN<PREFIX> {
     #[inline]
-    fn eq(&self, other: &Str) -> bool {
+    fn eq(&self, other: &StrN<PREFIX>) -> bool {
         if self.len != other.len {
             return false;
         }
@@ -181,8 +353,8 @@ impl PartialEq for Str {
             return false;
         }
 
-        if self.len > PREFIX_LENGTH as LenType {
-            let ptr_len = self.len as usize - PREFIX_LENGTH;
+        if self.len > PREFIX as LenType {
+            let ptr_len = self.len as usize - PREFIX;
             unsafe {
                 let a = slice::from_raw_parts(self.suffix, ptr_len);
                 let b = slice::from_raw_parts(other.suffix, ptr_len);
@@ -192,16 +364,53 @@ impl PartialEq for Str {
         true
     }
 }
-impl Display for Str {
+
+impl<const PREFIX: usize> Eq for StrN<PREFIX> {}
+
+impl<const PREFIX: usize> PartialOrd for StrN<PREFIX> {
+    #[inline]
+    fn partial_cmp(&self, other: &StrN<PREFIX>) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const PREFIX: usize> Ord for StrN<PREFIX> {
+    #[inline]
+    fn cmp(&self, other: &StrN<PREFIX>) -> core::cmp::Ordering {
+        let prefix_len = PREFIX.min(self.len()).min(other.len());
+        let prefix_ord = self.prefix[..prefix_len].cmp(&other.prefix[..prefix_len]);
+        if prefix_ord != core::cmp::Ordering::Equal {
+            return prefix_ord;
+        }
+
+        if self.len > PREFIX as LenType && other.len > PREFIX as LenType {
+            let self_suffix_len = self.len() - PREFIX;
+            let other_suffix_len = other.len() - PREFIX;
+            let suffix_len = self_suffix_len.min(other_suffix_len);
+            unsafe {
+                let a = slice::from_raw_parts(self.suffix, suffix_len);
+                let b = slice::from_raw_parts(other.suffix, suffix_len);
+                let suffix_ord = a.cmp(b);
+                if suffix_ord != core::cmp::Ordering::Equal {
+                    return suffix_ord;
+                }
+            }
+        }
+
+        self.len.cmp(&other.len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const PREFIX: usize> Display for StrN<PREFIX> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let prefix_str;
         let mut suffix_str = "";
         unsafe {
-            prefix_str =
-                core::str::from_utf8_unchecked(&self.prefix[0..PREFIX_LENGTH.min(self.len())]);
-            if self.len > PREFIX_LENGTH as LenType {
-                let ptr_len = self.len as usize - PREFIX_LENGTH;
+            prefix_str = core::str::from_utf8_unchecked(&self.prefix[0..PREFIX.min(self.len())]);
+            if self.len > PREFIX as LenType {
+                let ptr_len = self.len as usize - PREFIX;
                 suffix_str =
                     core::str::from_utf8_unchecked(slice::from_raw_parts(self.suffix, ptr_len));
             }
@@ -210,7 +419,8 @@ impl Display for Str {
     }
 }
 
-impl core::fmt::Debug for Str {
+#[cfg(feature = "std")]
+impl<const PREFIX: usize> core::fmt::Debug for StrN<PREFIX> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
@@ -219,13 +429,13 @@ impl core::fmt::Debug for Str {
             self,
             self.len,
             self.len as usize + self.capacity_offset as usize,
-            PREFIX_LENGTH,
-            0.max(self.len as usize + self.capacity_offset as usize - PREFIX_LENGTH),
+            PREFIX,
+            self.len().saturating_sub(PREFIX) + self.capacity_offset as usize,
         )
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use rand::distributions::Alphanumeric;
@@ -241,7 +451,7 @@ mod tests {
         assert_eq!(s.len, 0);
         assert!(s.is_empty());
         assert_eq!(s.capacity_offset, 0);
-        assert_eq!(s.prefix, [0; PREFIX_LENGTH]);
+        assert_eq!(s.prefix, [0; DEFAULT_PREFIX_LENGTH]);
         assert_eq!(s.suffix, null_mut());
     }
 
@@ -252,7 +462,7 @@ mod tests {
         assert_eq!(s.len, 3);
         assert!(!s.is_empty());
         assert_eq!(s.capacity_offset, 0);
-        let mut expected_prefix: [u8; PREFIX_LENGTH] = [0; PREFIX_LENGTH];
+        let mut expected_prefix: [u8; DEFAULT_PREFIX_LENGTH] = [0; DEFAULT_PREFIX_LENGTH];
         expected_prefix[..3].clone_from_slice("abc".as_bytes());
         assert_eq!(s.prefix, expected_prefix);
         assert_eq!(s.suffix, null_mut());
@@ -264,17 +474,17 @@ mod tests {
 
         assert_eq!(s.len, LONG_STR.len() as LenType);
         assert_eq!(s.capacity_offset, 0);
-        assert_eq!(s.prefix, LONG_STR.as_bytes()[..PREFIX_LENGTH]);
+        assert_eq!(s.prefix, LONG_STR.as_bytes()[..DEFAULT_PREFIX_LENGTH]);
         assert_ne!(s.suffix, null_mut());
 
-        let ptr_len = s.len as usize - PREFIX_LENGTH;
+        let ptr_len = s.len as usize - DEFAULT_PREFIX_LENGTH;
 
         let suffix_slice;
         unsafe {
             suffix_slice = slice::from_raw_parts(s.suffix, ptr_len);
         }
         let suffix_str = core::str::from_utf8(suffix_slice).unwrap();
-        assert_eq!(suffix_str, &LONG_STR[PREFIX_LENGTH..]);
+        assert_eq!(suffix_str, &LONG_STR[DEFAULT_PREFIX_LENGTH..]);
     }
 
     #[test]
@@ -282,7 +492,10 @@ mod tests {
         assert_eq!(Str::from("test")[2], "test".as_bytes()[2]);
 
         for i in 0..LONG_STR.len() {
-            assert_eq!(Str::from(LONG_STR)[i], LONG_STR.as_bytes()[i]);
+            assert_eq!(
+                Str::from(LONG_STR)[i],
+                LONG_STR.as_bytes()[i]
+            );
         }
     }
 
@@ -306,6 +519,34 @@ mod tests {
         assert_ne!(a, b);
     }
 
+    #[test]
+    fn test_ord_no_suffix() {
+        let a = Str::from("abc");
+        let b = Str::from("abd");
+
+        assert!(a < b);
+        assert!(b > a);
+        assert_eq!(a.cmp(&a), core::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_ord_with_suffix() {
+        let a = Str::from(LONG_STR);
+        let b = Str::from(LONG_STR2);
+
+        assert!(b < a); // 'l' < 't'
+        assert_eq!(a.cmp(&a), core::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_ord_prefix_vs_length() {
+        let short = Str::from(&LONG_STR[..DEFAULT_PREFIX_LENGTH]);
+        let long = Str::from(LONG_STR);
+
+        assert!(short < long);
+        assert!(long > short);
+    }
+
     fn rand_str(rand_len: usize) -> String {
         let r: String = thread_rng()
             .sample_iter(&Alphanumeric)
@@ -313,7 +554,7 @@ mod tests {
             .map(char::from)
             .collect();
 
-        return r;
+        r
     }
 
     #[test]
@@ -322,9 +563,9 @@ mod tests {
             for _ in 0..100 {
                 let s = rand_str(i);
                 let a = Str::from(&s);
-                let a2 = Str::from(&format!("{}^", s));
+                let a2 = Str::from(&format!("{s}^"));
                 let b = Str::from(&s);
-                let b2 = Str::from(&format!("{}_", s));
+                let b2 = Str::from(&format!("{s}_"));
 
                 assert_eq!(a, b);
                 assert_ne!(a2, b2);
@@ -337,16 +578,19 @@ mod tests {
         assert_eq!(Str::from("a").to_string(), "a".to_string());
         assert_eq!(Str::from("ab").to_string(), "ab".to_string());
         assert_eq!(Str::from("abc").to_string(), "abc".to_string());
-        assert_eq!(Str::from(LONG_STR).to_string(), LONG_STR.to_string());
+        assert_eq!(
+            Str::from(LONG_STR).to_string(),
+            LONG_STR.to_string()
+        );
     }
 
     #[test]
     fn test_starts_with_other() {
         let a = Str::from(LONG_STR);
         let b = Str::from(LONG_STR2);
-        let a_short = Str::from(&LONG_STR[..PREFIX_LENGTH]);
-        let b_short = Str::from(&LONG_STR2[..PREFIX_LENGTH]);
-        let a_shorter = Str::from(&LONG_STR[..PREFIX_LENGTH - 2]);
+        let a_short = Str::from(&LONG_STR[..DEFAULT_PREFIX_LENGTH]);
+        let b_short = Str::from(&LONG_STR2[..DEFAULT_PREFIX_LENGTH]);
+        let a_shorter = Str::from(&LONG_STR[..DEFAULT_PREFIX_LENGTH - 2]);
 
         assert!(a.starts_with(&a));
         assert!(b.starts_with(&b));
@@ -362,6 +606,87 @@ mod tests {
 
         assert!(a.starts_with(&a_shorter));
         assert!(a_short.starts_with(&a_shorter));
-        assert!(a_shorter.starts_with(&a_shorter))
+        assert!(a_shorter.starts_with(&a_shorter));
+    }
+
+    #[test]
+    fn test_push_str_within_prefix() {
+        let mut s = Str::from("ab");
+        s.push_str("cd");
+
+        assert_eq!(s.to_string(), "abcd");
+        assert_eq!(s.len(), 4);
+    }
+
+    #[test]
+    fn test_push_str_across_prefix_boundary() {
+        let mut s = Str::from(&"x".repeat(DEFAULT_PREFIX_LENGTH - 2));
+        s.push_str("abcdef");
+
+        let expected = format!("{}abcdef", "x".repeat(DEFAULT_PREFIX_LENGTH - 2));
+        assert_eq!(s.to_string(), expected);
+        assert_eq!(s.len(), expected.len());
+    }
+
+    #[test]
+    fn test_push_str_entirely_in_suffix() {
+        let mut s = Str::from(LONG_STR);
+        s.push_str(" and some more");
+
+        assert_eq!(s.to_string(), format!("{LONG_STR} and some more"));
+    }
+
+    #[test]
+    fn test_push_char() {
+        let mut s = Str::from("ab");
+        s.push('c');
+        assert_eq!(s.to_string(), "abc");
+    }
+
+    #[test]
+    fn test_reserve_reflected_in_capacity() {
+        let mut s = Str::from("ab");
+        s.reserve(50);
+
+        assert!(s.capacity() >= 52);
+        s.push_str(&"y".repeat(50));
+        assert_eq!(s.len(), 52);
+        assert!(s.capacity() >= 52);
+    }
+
+    #[test]
+    fn test_reserve_after_truncate_has_spare_capacity_offset() {
+        let mut s = Str::from(LONG_STR);
+        s.truncate(20);
+        s.push_str(&"Y".repeat(50));
+
+        assert_eq!(s.to_string(), format!("{}{}", &LONG_STR[..20], "Y".repeat(50)));
+        assert_eq!(s.len(), 70);
+        assert!(s.capacity() >= 70);
+    }
+
+    #[test]
+    fn test_truncate_and_clear() {
+        let mut s = Str::from(LONG_STR);
+        let original_capacity = s.capacity();
+
+        s.truncate(5);
+        assert_eq!(s.to_string(), &LONG_STR[..5]);
+        assert_eq!(s.capacity(), original_capacity);
+
+        s.clear();
+        assert!(s.is_empty());
+        assert_eq!(s.to_string(), "");
+    }
+
+    #[test]
+    fn test_insert_str() {
+        let mut s = Str::from("ac");
+        s.insert_str(1, "b");
+        assert_eq!(s.to_string(), "abc");
+
+        let mut long = Str::from(LONG_STR);
+        long.insert_str(0, "PREFIX: ");
+        assert_eq!(long.to_string(), format!("PREFIX: {LONG_STR}"));
     }
 }
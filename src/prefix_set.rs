@@ -0,0 +1,309 @@
+//! Matching many `Str` patterns against a query in roughly O(query length).
+//!
+//! [`PrefixSet`] indexes its patterns by the first `PREFIX` bytes in a byte
+//! trie, so the hot path walks the query byte-by-byte through inline
+//! prefixes and never chases a `suffix` pointer until it reaches a trie leaf
+//! holding a pattern longer than `PREFIX`. Each trie node keeps its
+//! out-edges as a small `(byte, Node)` list rather than a dense 256-entry
+//! table, the same sparse-transition trick aho-corasick uses for states
+//! with few children. The list is kept sorted by [`BYTE_RANK`], the same
+//! byte-frequency heuristic aho-corasick uses to pick a discriminating
+//! byte: rare bytes sort first, so a query byte that matches none of a
+//! node's children is usually rejected by the time the scan reaches the
+//! handful of common bytes near the end, and the scan can stop the moment
+//! it passes the query byte's own rank.
+
+use alloc::vec::Vec;
+use core::ptr::null_mut;
+
+use crate::{alloc_bytes, LenType, StrN, DEFAULT_PREFIX_LENGTH};
+
+/// Source of query bytes for [`PrefixSet`] lookups, implemented for both a
+/// plain `&str` and a `&Str`.
+pub trait QueryBytes {
+    fn query_len(&self) -> usize;
+    fn query_byte(&self, index: usize) -> u8;
+}
+
+impl QueryBytes for str {
+    #[inline]
+    fn query_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn query_byte(&self, index: usize) -> u8 {
+        self.as_bytes()[index]
+    }
+}
+
+impl<const PREFIX: usize> QueryBytes for StrN<PREFIX> {
+    #[inline]
+    fn query_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn query_byte(&self, index: usize) -> u8 {
+        if index < PREFIX {
+            self.prefix_bytes()[index]
+        } else {
+            unsafe { *self.suffix_ptr().add(index - PREFIX) }
+        }
+    }
+}
+
+/// Approximate per-byte frequency rank in typical ASCII text, lowest for
+/// the rarest bytes and highest for the most common. Used to order a
+/// node's children so lookups check the rarest (most discriminating) byte
+/// first, the same heuristic aho-corasick uses to pick a rare byte for
+/// fast rejection.
+#[rustfmt::skip]
+const BYTE_RANK: [u8; 256] = [
+    157, 156, 155, 154, 153, 152, 151, 150, 149, 159, 160, 148, 147, 158, 146, 145,
+    144, 143, 142, 141, 140, 139, 138, 137, 136, 135, 134, 133, 132, 131, 130, 129,
+    255, 183, 190, 174, 173, 172, 170, 189, 181, 180, 169, 168, 191, 188, 192, 186,
+    202, 201, 200, 199, 198, 197, 196, 195, 194, 193, 185, 184, 162, 167, 161, 182,
+    175, 226, 209, 217, 219, 228, 213, 212, 221, 224, 206, 207, 218, 215, 223, 225,
+    210, 204, 220, 222, 227, 216, 208, 214, 205, 211, 203, 179, 163, 178, 171, 187,
+    165, 252, 235, 243, 245, 254, 239, 238, 247, 250, 232, 233, 244, 241, 249, 251,
+    236, 230, 246, 248, 253, 242, 234, 240, 231, 237, 229, 177, 164, 176, 166, 128,
+    127, 126, 125, 124, 123, 122, 121, 120, 119, 118, 117, 116, 115, 114, 113, 112,
+    111, 110, 109, 108, 107, 106, 105, 104, 103, 102, 101, 100, 99, 98, 97, 96,
+    95, 94, 93, 92, 91, 90, 89, 88, 87, 86, 85, 84, 83, 82, 81, 80,
+    79, 78, 77, 76, 75, 74, 73, 72, 71, 70, 69, 68, 67, 66, 65, 64,
+    63, 62, 61, 60, 59, 58, 57, 56, 55, 54, 53, 52, 51, 50, 49, 48,
+    47, 46, 45, 44, 43, 42, 41, 40, 39, 38, 37, 36, 35, 34, 33, 32,
+    31, 30, 29, 28, 27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16,
+    15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0,
+];
+
+struct Node<const PREFIX: usize> {
+    children: Vec<(u8, Node<PREFIX>)>,
+    /// Patterns whose full length equals the depth of this node.
+    exact: Vec<StrN<PREFIX>>,
+    /// Patterns longer than `PREFIX` whose inline prefix matches this
+    /// node's path; only populated on nodes at depth `PREFIX`.
+    long: Vec<StrN<PREFIX>>,
+}
+
+impl<const PREFIX: usize> Default for Node<PREFIX> {
+    fn default() -> Self {
+        Self {
+            children: Vec::new(),
+            exact: Vec::new(),
+            long: Vec::new(),
+        }
+    }
+}
+
+impl<const PREFIX: usize> Node<PREFIX> {
+    /// Looks up the child for `byte`. `children` is sorted by [`BYTE_RANK`],
+    /// so the scan stops as soon as it passes `byte`'s rank rather than
+    /// walking the whole list on a miss.
+    fn child(&self, byte: u8) -> Option<&Node<PREFIX>> {
+        let target_rank = BYTE_RANK[byte as usize];
+        for (b, node) in &self.children {
+            let rank = BYTE_RANK[*b as usize];
+            if rank == target_rank {
+                return Some(node);
+            }
+            if rank > target_rank {
+                break;
+            }
+        }
+        None
+    }
+
+    fn child_or_insert(&mut self, byte: u8) -> &mut Node<PREFIX> {
+        let target_rank = BYTE_RANK[byte as usize];
+        let mut insert_at = self.children.len();
+        for (i, (b, _)) in self.children.iter().enumerate() {
+            let rank = BYTE_RANK[*b as usize];
+            if rank == target_rank {
+                return &mut self.children[i].1;
+            }
+            if rank > target_rank {
+                insert_at = i;
+                break;
+            }
+        }
+        self.children.insert(insert_at, (byte, Node::default()));
+        &mut self.children[insert_at].1
+    }
+}
+
+/// Duplicates a `Str`'s bytes into a fresh allocation so the set can own its
+/// own copy of every pattern it indexes.
+fn duplicate<const PREFIX: usize>(pattern: &StrN<PREFIX>) -> StrN<PREFIX> {
+    let len = pattern.len();
+    let prefix = *pattern.prefix_bytes();
+
+    let mut suffix = null_mut();
+    if len > PREFIX {
+        let suffix_len = len - PREFIX;
+        unsafe {
+            suffix = alloc_bytes(suffix_len);
+            core::ptr::copy_nonoverlapping(pattern.suffix_ptr(), suffix, suffix_len);
+        }
+    }
+
+    StrN::from_raw_parts(len as LenType, prefix, 0, suffix)
+}
+
+/// A set of `Str` patterns searchable by "is this pattern a prefix of my
+/// query" in roughly O(query length) rather than O(patterns × length).
+pub struct PrefixSet<const PREFIX: usize = DEFAULT_PREFIX_LENGTH> {
+    root: Node<PREFIX>,
+}
+
+impl<const PREFIX: usize> PrefixSet<PREFIX> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            root: Node::default(),
+        }
+    }
+
+    /// Indexes `pattern` so future queries can match it.
+    pub fn insert(&mut self, pattern: &StrN<PREFIX>) {
+        let owned = duplicate(pattern);
+        let depth = PREFIX.min(owned.len());
+
+        let mut node = &mut self.root;
+        for i in 0..depth {
+            node = node.child_or_insert(owned.prefix_bytes()[i]);
+        }
+
+        if owned.len() <= PREFIX {
+            node.exact.push(owned);
+        } else {
+            node.long.push(owned);
+        }
+    }
+
+    /// Returns every indexed pattern that is a prefix of `query`.
+    pub fn matching_prefixes<'a, Q: QueryBytes + ?Sized>(
+        &'a self,
+        query: &Q,
+    ) -> impl Iterator<Item = &'a StrN<PREFIX>> {
+        let query_len = query.query_len();
+        let mut matches: Vec<&StrN<PREFIX>> = Vec::new();
+
+        let mut node = &self.root;
+        matches.extend(node.exact.iter());
+
+        let depth_limit = PREFIX.min(query_len);
+        let mut reached_full_depth = false;
+        for i in 0..depth_limit {
+            match node.child(query.query_byte(i)) {
+                Some(child) => {
+                    node = child;
+                    matches.extend(node.exact.iter());
+                    reached_full_depth = i + 1 == PREFIX;
+                }
+                None => break,
+            }
+        }
+
+        if reached_full_depth {
+            for pattern in &node.long {
+                let suffix_len = pattern.len() - PREFIX;
+                if suffix_len <= query_len - PREFIX
+                    && (0..suffix_len).all(|i| pattern.query_byte(PREFIX + i) == query.query_byte(PREFIX + i))
+                {
+                    matches.push(pattern);
+                }
+            }
+        }
+
+        matches.into_iter()
+    }
+
+    /// Fast path that stops at the first matching pattern, avoiding the
+    /// allocation `matching_prefixes` needs to collect every match.
+    #[must_use]
+    pub fn contains_prefix_of<Q: QueryBytes + ?Sized>(&self, query: &Q) -> bool {
+        let query_len = query.query_len();
+
+        let mut node = &self.root;
+        if !node.exact.is_empty() {
+            return true;
+        }
+
+        let depth_limit = PREFIX.min(query_len);
+        for i in 0..depth_limit {
+            match node.child(query.query_byte(i)) {
+                Some(child) => {
+                    node = child;
+                    if !node.exact.is_empty() {
+                        return true;
+                    }
+                    if i + 1 == PREFIX {
+                        return node.long.iter().any(|pattern| {
+                            let suffix_len = pattern.len() - PREFIX;
+                            suffix_len <= query_len - PREFIX
+                                && (0..suffix_len).all(|j| {
+                                    pattern.query_byte(PREFIX + j) == query.query_byte(PREFIX + j)
+                                })
+                        });
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        false
+    }
+}
+
+impl<const PREFIX: usize> Default for PrefixSet<PREFIX> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Str;
+
+    #[test]
+    fn test_short_patterns() {
+        let mut set: PrefixSet = PrefixSet::new();
+        set.insert(&Str::from("a"));
+        set.insert(&Str::from("ab"));
+        set.insert(&Str::from("b"));
+
+        assert!(set.contains_prefix_of("abcdef"));
+        assert_eq!(set.matching_prefixes("abcdef").count(), 2);
+        assert!(!set.contains_prefix_of("cdef"));
+        assert_eq!(set.matching_prefixes("cdef").count(), 0);
+    }
+
+    #[test]
+    fn test_long_patterns_need_suffix_check() {
+        let mut set: PrefixSet = PrefixSet::new();
+        let a: Str = Str::from("this is a longer string that matches");
+        let b: Str = Str::from("this is a longer string but diverges here");
+        set.insert(&a);
+        set.insert(&b);
+
+        assert!(set.contains_prefix_of("this is a longer string that matches and then some"));
+        assert!(!set.contains_prefix_of("this is a longer string that does not match either"));
+        assert_eq!(
+            set.matching_prefixes("this is a longer string that matches and then some")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_query_by_str_value() {
+        let mut set: PrefixSet = PrefixSet::new();
+        set.insert(&Str::from("abc"));
+
+        let query: Str = Str::from("abcdef");
+        assert!(set.contains_prefix_of(&query));
+    }
+}